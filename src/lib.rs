@@ -63,10 +63,86 @@
 //! }
 //! ```
 //!
+//! ## Adding context
+//!
+//! The `TerminatorExt` trait adds `.context()` and `.with_context()` to any `Result` or
+//! `Option` so you can attach a message before propagating it with `?`, without having to
+//! define a custom error enum for every call site:
+//!
+//! ```ignore
+//! use terminator::{Terminator, TerminatorExt};
+//!
+//! fn main() -> Result<(), Terminator> {
+//!   let contents = std::fs::read_to_string("users.toml")
+//!     .context("while loading users.toml")?;
+//!   // your other code
+//!   Ok(())
+//! }
+//! ```
+//!
+//! ## Custom exit codes
+//!
+//! By default the process always exits with `1` on error, same as before. To pick a different
+//! code per error (e.g. the `sysexits.h`-style `EX_USAGE = 64`), implement `ExitCode` for your
+//! error type and return `Terminator<E>` directly from `main` instead of `Result<(),
+//! Terminator<E>>` -- `std`'s blanket `Termination` impl for `Result` always reports
+//! `ExitCode::FAILURE` regardless of `E`, so bypassing it is what lets `Terminator` pick the
+//! code:
+//!
+//! ```ignore
+//! use std::fmt;
+//! use terminator::{Chain, ExitCode, Terminator};
+//!
+//! struct MyError(String);
+//!
+//! impl fmt::Display for MyError {
+//!   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+//!     write!(f, "{}", self.0)
+//!   }
+//! }
+//!
+//! // `MyError` doesn't implement `std::error::Error`, so it opts out of chain printing with
+//! // the default, empty `Chain` impl.
+//! impl Chain for MyError {}
+//!
+//! impl ExitCode for MyError {
+//!   fn code(&self) -> u8 { 64 } // EX_USAGE
+//! }
+//!
+//! fn main() -> Terminator<MyError> {
+//!   match run() {
+//!     Ok(()) => Terminator::ok(),
+//!     Err(e) => e.into(),
+//!   }
+//! }
+//! ```
+//!
+//! ## Backtraces
+//!
+//! With the `backtrace` feature enabled, the `From` conversion that every `?` funnels an error
+//! through also captures a `std::backtrace::Backtrace`. When `RUST_BACKTRACE=1` or
+//! `RUST_LIB_BACKTRACE=1` is set, `Terminator`'s own `Termination::report` (see "Custom exit
+//! codes" above) prints it after the error and its `Caused by:` lines, same as it would be for a
+//! panic. The `Debug` output, which is what `fn main() -> Result<(), Terminator>` prints, does not
+//! include it. This is off by default so the crate stays free of the extra output for end users who
+//! don't need it.
+//!
+//! ## Colorized output
+//!
+//! With the `color` feature enabled, `Terminator`'s own `Termination::report` (see "Custom exit
+//! codes" above) prints the `Error: ` prefix in bold red and each `Caused by:` line dimmed, but
+//! only when stderr is actually a terminal -- redirect or pipe it and the output is plain text,
+//! same as without the feature. This is opt-in so the crate stays dependency-free by default.
+//!
 //! ## Minimum version
-//! We support a minimum `rustc` version of `1.26.0` as this was when the question
-//! mark in main feature was stabilized. However, for versions less than `1.31.0`
-//! you'll need to set the feature flag `rust2015` in your `Cargo.toml` like so:
+//! We support a minimum `rustc` version of `1.61.0`, as this is when `std::process::Termination`
+//! (what lets `Terminator<E>` be returned directly from `main`, see "Custom exit codes" above)
+//! was stabilized for use outside of `std` itself. The `backtrace` feature needs `1.65.0`
+//! (`std::backtrace::Backtrace`) and the `color` feature needs `1.70.0` (`std::io::IsTerminal`).
+//!
+//! `rust2015` isn't about an older `rustc` anymore: it only spells the default error type as
+//! `Box<Error>` instead of `Box<dyn Error>` (the same type). Every supported `rustc` accepts `dyn`,
+//! and the crate's other `Box<dyn Error + ...>` impls use it either way. Set it like so:
 //!
 //! ```toml
 //! [dependencies]
@@ -75,6 +151,10 @@
 
 use std::error::Error;
 use std::fmt::{self, Debug, Display};
+use std::process;
+
+#[cfg(feature = "backtrace")]
+use std::backtrace::{Backtrace, BacktraceStatus};
 
 #[doc(hidden)]
 #[cfg(not(rust2015))]
@@ -86,22 +166,280 @@ type DefaultError = Box<Error>;
 
 /// A type that lets you output your error as `Display` for `fn main() -> Result<(), Error>`
 pub struct Terminator<E = DefaultError> {
-    err: E
+    err: Option<E>,
+    #[cfg(feature = "backtrace")]
+    backtrace: Option<Backtrace>,
+}
+
+impl<E> Terminator<E> {
+    /// A `Terminator` that represents a successful run, i.e. exit code `0`.
+    ///
+    /// Used when returning `Terminator<E>` directly from `main` (see [`ExitCode`]) instead of
+    /// going through `Result<(), Terminator<E>>`.
+    pub fn ok() -> Self {
+        Self {
+            err: None,
+            #[cfg(feature = "backtrace")]
+            backtrace: None,
+        }
+    }
 }
 
 impl<T: Into<E> + Display, E> From<T> for Terminator<E> {
+    // `From` is the single choke point every `?` funnels an error through on its way into a
+    // `Terminator`, which makes it the natural place to snapshot the stack for the
+    // `backtrace` feature.
     fn from(err: T) -> Self {
         Self {
-            err: err.into(),
+            err: Some(err.into()),
+            #[cfg(feature = "backtrace")]
+            backtrace: Some(Backtrace::capture()),
+        }
+    }
+}
+
+/// Lets an error type pick the process exit code `Terminator` reports, instead of always
+/// exiting with `1`.
+///
+/// Returning `Terminator<E>` directly from `main` (rather than `Result<(), Terminator<E>>`)
+/// bypasses `std`'s blanket `Termination` impl for `Result`, which always exits with
+/// `ExitCode::FAILURE` on `Err` no matter what `E` is. Implement this trait on your error type
+/// and override `code` to pick, e.g., the `sysexits.h`-style `EX_USAGE = 64`.
+///
+/// This returns `u8` rather than `i32` because that's what `process::ExitCode::from` actually
+/// accepts -- an `i32` here would just get silently truncated on the way out (and most codes
+/// above `255` are not portable anyway; see `std::process::ExitCode`'s own docs).
+pub trait ExitCode {
+    /// The process exit code to report. Defaults to `1`, matching the old behavior.
+    fn code(&self) -> u8 {
+        1
+    }
+}
+
+#[cfg(not(rust2015))]
+impl ExitCode for Box<dyn Error> {}
+#[cfg(rust2015)]
+impl ExitCode for Box<Error> {}
+
+impl ExitCode for Context {}
+
+/// Exposes an error's `source()` chain so `Termination::report` can walk it when printing to
+/// stderr.
+///
+/// This can't be blanket-implemented for every `E: std::error::Error` -- common error
+/// containers like `Box<dyn Error>` don't implement `Error` themselves (only `Sized` concrete
+/// error types do), and a blanket impl plus a specific one for `Box<dyn Error>` runs into a
+/// coherence hazard (rustc reserves the right to add that impl upstream later). So, like
+/// [`ExitCode`], this is an explicit opt-in per error type. The default `source` returns `None`,
+/// so a type that doesn't implement `std::error::Error` at all (or just doesn't want chain
+/// printing) can opt in with an empty `impl Chain for MyError {}`.
+pub trait Chain {
+    /// The next error in the chain, if any.
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+impl Chain for Context {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Error::source(self)
+    }
+}
+
+#[cfg(not(rust2015))]
+impl Chain for Box<dyn Error> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Error::source(&**self)
+    }
+}
+#[cfg(rust2015)]
+impl Chain for Box<Error> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Error::source(&**self)
+    }
+}
+
+impl Chain for Box<dyn Error + Send> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Error::source(&**self)
+    }
+}
+
+impl Chain for Box<dyn Error + Send + Sync> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Error::source(&**self)
+    }
+}
+
+impl<E: Display + Chain + ExitCode> process::Termination for Terminator<E> {
+    fn report(self) -> process::ExitCode {
+        match self.err {
+            Some(err) => {
+                let code = err.code();
+                #[cfg_attr(not(feature = "backtrace"), allow(unused_mut))]
+                let mut report = chain_report(&err);
+                #[cfg(feature = "backtrace")]
+                if let Some(backtrace) =
+                    self.backtrace.as_ref().filter(|b| b.status() == BacktraceStatus::Captured)
+                {
+                    report.push('\n');
+                    report.push_str(&backtrace.to_string());
+                }
+                eprintln!("{}{}", error_header(), report);
+                process::ExitCode::from(code)
+            }
+            None => process::ExitCode::SUCCESS,
+        }
+    }
+}
+
+/// An error produced by [`TerminatorExt::context`] or [`TerminatorExt::with_context`].
+///
+/// Its `Display` is the message that was attached at the call site, and its `source()` is
+/// whatever error (if any) it was attached to, so it composes with the `source()` chain printed
+/// by `Terminator`'s `Termination::report`.
+pub struct Context {
+    msg: String,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl Display for Context {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+
+impl Debug for Context {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+
+impl Error for Context {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_ref().map(|e| e.as_ref() as &(dyn Error + 'static))
+    }
+}
+
+/// Attach a human-readable message to a `Result` or `Option` before propagating it into a
+/// `Terminator` return with `?`.
+///
+/// ```ignore
+/// use terminator::{Terminator, TerminatorExt};
+///
+/// fn run() -> Result<(), Terminator> {
+///     let contents = std::fs::read_to_string("users.toml")
+///         .context("while loading users.toml")?;
+///     Ok(())
+/// }
+/// ```
+pub trait TerminatorExt<T> {
+    /// Attach `context` as the message of the error, if any.
+    fn context<C: Display>(self, context: C) -> Result<T, Context>;
+
+    /// Lazily attach the message returned by `f` as the message of the error, if any.
+    fn with_context<C: Display, F: FnOnce() -> C>(self, f: F) -> Result<T, Context>;
+}
+
+impl<T, E: Error + Send + Sync + 'static> TerminatorExt<T> for Result<T, E> {
+    fn context<C: Display>(self, context: C) -> Result<T, Context> {
+        self.map_err(|err| Context {
+            msg: context.to_string(),
+            source: Some(Box::new(err)),
+        })
+    }
+
+    fn with_context<C: Display, F: FnOnce() -> C>(self, f: F) -> Result<T, Context> {
+        self.map_err(|err| Context {
+            msg: f().to_string(),
+            source: Some(Box::new(err)),
+        })
+    }
+}
+
+impl<T> TerminatorExt<T> for Option<T> {
+    fn context<C: Display>(self, context: C) -> Result<T, Context> {
+        self.ok_or_else(|| Context {
+            msg: context.to_string(),
+            source: None,
+        })
+    }
+
+    fn with_context<C: Display, F: FnOnce() -> C>(self, f: F) -> Result<T, Context> {
+        self.ok_or_else(|| Context {
+            msg: f().to_string(),
+            source: None,
+        })
+    }
+}
+
+// TTY-aware ANSI styling for the `color` feature. Detected at print time rather than cached, so
+// output stays plain when stderr is piped or redirected even if it's a terminal at startup.
+#[cfg(feature = "color")]
+fn stderr_is_tty() -> bool {
+    use std::io::IsTerminal;
+    std::io::stderr().is_terminal()
+}
+
+/// The `Error: ` prefix `Terminator`'s own `Termination::report` writes before the error. With
+/// the `color` feature enabled and stderr attached to a terminal it's bold red, matching the
+/// style hand-rolled `eprintln!`-based CLI error handling usually reaches for.
+///
+/// Only `Termination::report` calls this -- `Debug` stays plain so coloring never leaks into a
+/// `format!("{:?}", term)` that isn't actually headed for a terminal.
+#[doc(hidden)]
+fn error_header() -> &'static str {
+    #[cfg(feature = "color")]
+    {
+        if stderr_is_tty() {
+            return "\x1b[1;31mError: \x1b[0m";
+        }
+    }
+    "Error: "
+}
+
+/// Formats one `source()` chain link for `chain_report`, dimmed when the `color` feature is
+/// enabled and stderr is a terminal.
+fn caused_by_line(err: &dyn Display) -> String {
+    #[cfg(feature = "color")]
+    {
+        if stderr_is_tty() {
+            return format!("\x1b[2mCaused by: {}\x1b[0m", err);
         }
     }
+    format!("Caused by: {}", err)
+}
+
+/// Renders `err`'s `Display` output followed by one `Caused by:` line per link in its `source()`
+/// chain (see [`Chain`]), the way `Termination::report` prints an error to stderr. Kept separate
+/// from `Debug` (below) so chain-walking only ever requires `Chain` where it's actually needed.
+fn chain_report<E: Display + Chain>(err: &E) -> String {
+    let mut report = format!("{}", err);
+    let mut source = Chain::source(err);
+    while let Some(err) = source {
+        report.push('\n');
+        report.push_str(&caused_by_line(&err));
+        source = err.source();
+    }
+    report
 }
 
-/// A manually implemented implementation of `Debug` that writes the error out to stderr as if it
-/// was `Display`
+/// Writes the error out as plain `Display`, with no `source()` chain, backtrace, or color.
+///
+/// This only requires `E: Display` -- the same bound `Terminator<E>` itself carries -- so it
+/// compiles for every error type `Terminator` can hold, matching the crate's promise that any
+/// `Display` error "just works". Bounding this impl on [`Chain`] (to walk the `source()` chain
+/// here too) would make `?` in `main` stop compiling for downstream error types that can't
+/// implement a foreign trait like `Chain` for a foreign type like `std::io::Error` (the orphan
+/// rule) -- a regression with no workaround. Chain, backtrace, and color output are only
+/// available through `chain_report` and `Termination::report`, which already require the extra
+/// bounds for other reasons.
 impl<E: Display> Debug for Terminator<E> {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(f, "{}", self.err)
+        match &self.err {
+            Some(err) => write!(f, "{}", err),
+            None => Ok(()),
+        }
     }
 }
 
@@ -133,3 +471,101 @@ fn terminator_can_be_used_anywhere_question_mark_can() {
 
     assert_eq!("oh no: hi", format!("{:?}", return_my_error().unwrap_err()));
 }
+
+#[test]
+fn terminator_prints_the_full_source_chain() {
+    #[derive(Debug)]
+    struct Root;
+
+    impl Display for Root {
+        fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+            write!(f, "no such file or directory")
+        }
+    }
+
+    impl Error for Root {}
+
+    #[derive(Debug)]
+    struct Wrapper(Root);
+
+    impl Display for Wrapper {
+        fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+            write!(f, "failed to read config")
+        }
+    }
+
+    impl Error for Wrapper {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    impl Chain for Wrapper {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Error::source(self)
+        }
+    }
+
+    assert_eq!(
+        "failed to read config\nCaused by: no such file or directory",
+        chain_report(&Wrapper(Root))
+    );
+}
+
+#[test]
+fn context_wraps_a_result_err_with_a_message_and_source() {
+    use std::io;
+
+    let res: Result<(), io::Error> = Err(io::Error::new(io::ErrorKind::NotFound, "missing"));
+    let err = res.context("while loading users.toml").unwrap_err();
+
+    assert_eq!(
+        "while loading users.toml\nCaused by: missing",
+        chain_report(&err)
+    );
+}
+
+#[test]
+fn context_converts_a_none_option_into_an_error() {
+    let none: Option<()> = None;
+    let err = none.context("user not found").unwrap_err();
+
+    assert_eq!("user not found", format!("{}", err));
+    assert!(Error::source(&err).is_none());
+}
+
+#[test]
+fn exit_code_defaults_to_one() {
+    struct PlainError;
+    impl ExitCode for PlainError {}
+
+    assert_eq!(1, PlainError.code());
+}
+
+#[test]
+fn error_header_and_caused_by_line_are_plain_without_the_color_feature() {
+    assert_eq!("Error: ", error_header());
+    assert_eq!("Caused by: oh no", caused_by_line(&"oh no"));
+}
+
+#[test]
+fn terminator_debug_never_applies_color_or_the_chain() {
+    // `error_header`/`caused_by_line` are only ever reached through `chain_report` and
+    // `Termination::report` -- `Debug` must stay a bare `Display` passthrough so a caller who
+    // does `format!("{:?}", term)` (to log to a file, or from a panicking `.unwrap_err()`) never
+    // gets ANSI escapes or `source()` chain lines baked in, regardless of the `color` feature.
+    let term = Terminator::<&str>::from("oh no");
+    assert_eq!("oh no", format!("{:?}", term));
+}
+
+#[test]
+fn exit_code_can_be_overridden() {
+    struct UsageError;
+    impl ExitCode for UsageError {
+        fn code(&self) -> u8 {
+            64
+        }
+    }
+
+    assert_eq!(64, UsageError.code());
+}